@@ -0,0 +1,266 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorCode {
+    // Lexer
+    UnexpectedCharacter, // E0001
+    InvalidNumber,       // E0002
+    IntegerOutOfRange,   // E0003
+    UnterminatedString,  // E0004
+    InvalidEscape,       // E0005
+    TokenTooLong,        // E0006
+    LineTooLong,         // E0007
+    UnterminatedComment, // E0008
+    UnterminatedChar,    // E0009
+    InvalidChar,         // E0010
+
+    // Parser
+    UnexpectedToken,     // E0100
+    ExpectedToken,       // E0101
+    UnterminatedBlock,   // E0102
+    InvalidAssignTarget, // E0103
+    DuplicateParameter,  // E0104
+}
+
+impl ErrorCode {
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::UnexpectedCharacter,
+        ErrorCode::InvalidNumber,
+        ErrorCode::IntegerOutOfRange,
+        ErrorCode::UnterminatedString,
+        ErrorCode::InvalidEscape,
+        ErrorCode::TokenTooLong,
+        ErrorCode::LineTooLong,
+        ErrorCode::UnterminatedComment,
+        ErrorCode::UnterminatedChar,
+        ErrorCode::InvalidChar,
+        ErrorCode::UnexpectedToken,
+        ErrorCode::ExpectedToken,
+        ErrorCode::UnterminatedBlock,
+        ErrorCode::InvalidAssignTarget,
+        ErrorCode::DuplicateParameter,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCode::UnexpectedCharacter => "E0001",
+            ErrorCode::InvalidNumber => "E0002",
+            ErrorCode::IntegerOutOfRange => "E0003",
+            ErrorCode::UnterminatedString => "E0004",
+            ErrorCode::InvalidEscape => "E0005",
+            ErrorCode::TokenTooLong => "E0006",
+            ErrorCode::LineTooLong => "E0007",
+            ErrorCode::UnterminatedComment => "E0008",
+            ErrorCode::UnterminatedChar => "E0009",
+            ErrorCode::InvalidChar => "E0010",
+
+            ErrorCode::UnexpectedToken => "E0100",
+            ErrorCode::ExpectedToken => "E0101",
+            ErrorCode::UnterminatedBlock => "E0102",
+            ErrorCode::InvalidAssignTarget => "E0103",
+            ErrorCode::DuplicateParameter => "E0104",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<ErrorCode> {
+        ErrorCode::ALL
+            .iter()
+            .find(|c| c.code().eq_ignore_ascii_case(code))
+            .copied()
+    }
+
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            ErrorCode::UnexpectedCharacter => {
+                "A character was found that cannot start or continue a token.
+
+Example:
+
+    x = 1 # 2
+
+'#' is not part of Viper's syntax. Identifiers may only contain letters,
+digits and underscores, and operators must be one of the supported
+symbols."
+            }
+            ErrorCode::InvalidNumber => {
+                "A numeric literal is malformed.
+
+Example:
+
+    x = 1.2.3
+    y = 12abc
+    z = 5.0i
+    w = 1e
+    v = 0b102
+    u = 0x
+
+A float literal may contain at most one decimal point. A number may
+end with one type suffix, 'i' for an integer or 'f' for a float, but
+'i' cannot follow a float literal. An exponent ('e' or 'E', then an
+optional sign) must be followed by at least one digit, as in 2.5e-3.
+Literals prefixed with 0x, 0o or 0b need at least one digit, and every
+digit must be valid for that base. Apart from these, a number must be
+followed by whitespace or an operator, not by letters. Digit separators
+('_') may only appear between two digits, as in 1_000."
+            }
+            ErrorCode::IntegerOutOfRange => {
+                "An integer literal does not fit in a 64-bit signed integer.
+
+Example:
+
+    x = 9223372036854775808
+
+Integers must lie between -9223372036854775808 and 9223372036854775807.
+Use a float literal if you need a larger magnitude."
+            }
+            ErrorCode::UnterminatedString => {
+                "A string literal was opened but never closed.
+
+Example:
+
+    name = \"viper
+
+Every string must end with a closing '\"' before the end of the file."
+            }
+            ErrorCode::InvalidEscape => {
+                "A string literal contains an unknown escape sequence.
+
+Example:
+
+    path = \"C:\\dir\"
+
+Supported escapes are \\n, \\r, \\t, \\0, \\', \\\", \\\\, \\xNN with
+exactly two hex digits, and \\u{...} with 1 to 6 hex digits naming a
+unicode scalar value. Write a literal backslash as \\\\."
+            }
+            ErrorCode::TokenTooLong => {
+                "An identifier or string literal is longer than the configured
+maximum token length.
+
+This limit is off by default and is only enforced when the lexer is
+configured with one, for example when lexing untrusted input. Split
+long strings up or shorten the identifier."
+            }
+            ErrorCode::LineTooLong => {
+                "A source line is longer than the configured maximum line length.
+
+This limit is off by default and is only enforced when the lexer is
+configured with one, for example when lexing untrusted input. Break the
+line up across several lines."
+            }
+            ErrorCode::UnterminatedComment => {
+                "A block comment was opened with '/*' but never closed.
+
+Example:
+
+    /* outer /* inner */
+    x = 1
+
+Block comments nest, so every '/*' needs its own matching '*/'. Here
+the outer comment is still open at the end of the file."
+            }
+            ErrorCode::UnterminatedChar => {
+                "A character literal was opened with a single quote but not closed
+on the same line.
+
+Example:
+
+    c = 'a
+
+Close the literal with a matching single quote: c = 'a'"
+            }
+            ErrorCode::InvalidChar => {
+                "A character literal must contain exactly one character.
+
+Example:
+
+    a = ''
+    b = 'ab'
+
+Use a string literal (\"ab\") for zero or several characters. Escape
+sequences such as '\\n' count as a single character."
+            }
+            ErrorCode::UnexpectedToken => {
+                "The parser found a token that cannot start a statement or
+expression at this point.
+
+Example:
+
+    ) x = 1
+
+Check for stray operators or closing delimiters before the highlighted
+token."
+            }
+            ErrorCode::ExpectedToken => {
+                "The parser required a specific token, such as a '{' opening a
+block, but found something else.
+
+Example:
+
+    if x
+        return 1
+
+Blocks must be wrapped in braces: if x { return 1 }"
+            }
+            ErrorCode::UnterminatedBlock => {
+                "A block was opened with '{' but the file ended before the
+matching '}'.
+
+Example:
+
+    if x {
+        return 1
+
+Add the missing '}' to close the block."
+            }
+            ErrorCode::InvalidAssignTarget => {
+                "The left-hand side of an assignment is not something that can be
+assigned to.
+
+Example:
+
+    1 = 2
+    a + b = 3
+    a[0] += 1
+
+Only a plain variable name may appear on the left of '=' or of a
+compound assignment such as '+='."
+            }
+            ErrorCode::DuplicateParameter => {
+                "A function declares two parameters with the same name.
+
+Example:
+
+    fn add(a: int, a: int) => int {
+        return a + a
+    }
+
+Each parameter must have a distinct name, otherwise it is unclear which
+argument the name refers to in the body. Rename one of them."
+            }
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip() {
+        assert_eq!(
+            ErrorCode::from_code("e0005"),
+            Some(ErrorCode::InvalidEscape)
+        );
+
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.code()), Some(*code));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod codes;
+
+pub use self::codes::*;
@@ -2,16 +2,26 @@ use std::fmt;
 
 use colored::Colorize;
 
+use super::super::diagnostics::ErrorCode;
 use super::{Token, TokenType};
 
+#[derive(Debug)]
+pub struct SpanEnd {
+    pub line: u32,
+    pub column: u32,
+    pub line_context: String,
+}
+
 #[derive(Debug)]
 pub struct TokenisationError {
+    pub code: ErrorCode,
     pub line: u32,
     pub column: u32,
     pub index: u32,
     pub filename: String,
     pub message: String,
     pub line_context: String,
+    pub end: Option<SpanEnd>,
 }
 
 impl fmt::Display for TokenisationError {
@@ -26,16 +36,50 @@ impl fmt::Display for TokenisationError {
 
         write!(
             f,
-            "Tokenisation failed: {}\n {} {}:{}:{} ({})\n\n   {}\n   {}",
-            self.message,
-            arrow,
-            self.filename,
-            self.line,
-            self.column,
-            self.index,
-            self.line_context,
-            padding.yellow().bold(),
-        )
+            "Tokenisation failed [{}]: {}\n {} {}:{}:{} ({})\n\n",
+            self.code, self.message, arrow, self.filename, self.line, self.column, self.index,
+        )?;
+
+        match &self.end {
+            Some(end) if end.line != self.line => {
+                let width = self.line_context.chars().count() as u32;
+                padding.push_str(&"~".repeat(width.saturating_sub(self.column) as usize));
+
+                writeln!(
+                    f,
+                    "   {}\n   {}",
+                    self.line_context,
+                    padding.yellow().bold()
+                )?;
+
+                if end.line > self.line + 1 {
+                    writeln!(f, "   {}", "...".blue().bold())?;
+                }
+
+                let underline = "~".repeat(end.column.saturating_sub(1) as usize);
+                write!(
+                    f,
+                    "   {}\n   {}",
+                    end.line_context,
+                    underline.yellow().bold()
+                )
+            }
+            Some(end) => {
+                padding.push_str(&"~".repeat(end.column.saturating_sub(self.column + 1) as usize));
+                write!(
+                    f,
+                    "   {}\n   {}",
+                    self.line_context,
+                    padding.yellow().bold()
+                )
+            }
+            None => write!(
+                f,
+                "   {}\n   {}",
+                self.line_context,
+                padding.yellow().bold()
+            ),
+        }
     }
 }
 
@@ -43,29 +87,45 @@ type TokenisationResult = Result<Token, TokenisationError>;
 
 pub struct Lexer {
     pub filename: String,
-    pub source: String,
+    pub source: Vec<char>,
     pub index: u32,
     pub line: u32,
     pub column: u32,
+    pub token_line: u32,
+    pub token_column: u32,
+    pub token_index: u32,
     pub lines: Vec<String>,
+    pub max_token_length: Option<u32>,
+    pub max_line_length: Option<u32>,
+    pub recover_errors: bool,
 }
 
 impl Lexer {
     pub fn new(filename: String, source: String) -> Lexer {
-        let lines = source.clone().lines().map(|s| s.to_string()).collect();
+        let lines = source
+            .replace("\r\n", "\n")
+            .split(['\n', '\r'])
+            .map(|s| s.to_string())
+            .collect();
 
         Lexer {
             filename,
-            source,
+            source: source.chars().collect(),
             index: 0,
             line: 1,
             column: 1,
+            token_line: 1,
+            token_column: 1,
+            token_index: 0,
             lines: lines,
+            max_token_length: None,
+            max_line_length: None,
+            recover_errors: false,
         }
     }
 
     fn advance(&mut self) -> Option<char> {
-        let c = self.source.chars().nth(self.index as usize);
+        let c = self.source.get(self.index as usize).copied();
         if c.is_some() {
             self.index += 1;
             self.column += 1;
@@ -74,29 +134,101 @@ impl Lexer {
     }
 
     fn peek(&self, offset: u32) -> Option<char> {
-        self.source.chars().nth((self.index + offset) as usize)
+        self.source.get((self.index + offset) as usize).copied()
+    }
+
+    fn is_line_end(&self) -> bool {
+        matches!(self.peek(0), None | Some('\n') | Some('\r'))
+    }
+
+    fn skip_newline(&mut self) {
+        if self.advance() == Some('\r') && self.peek(0) == Some('\n') {
+            self.advance();
+        }
+
+        self.line += 1;
+        self.column = 1;
+    }
+
+    fn skip_block_comment(&mut self) -> Result<(), TokenisationError> {
+        let (start_line, start_column, start_index) = (self.line, self.column, self.index);
+
+        self.advance();
+        self.advance();
+
+        let mut depth = 1;
+
+        while depth > 0 {
+            match (self.peek(0), self.peek(1)) {
+                (None, _) => {
+                    return Err(self.error_spanning(
+                        ErrorCode::UnterminatedComment,
+                        "Unterminated block comment".to_string(),
+                        start_line,
+                        start_column,
+                        start_index,
+                    ));
+                }
+                (Some('/'), Some('*')) => {
+                    self.advance();
+                    self.advance();
+                    depth += 1;
+                }
+                (Some('*'), Some('/')) => {
+                    self.advance();
+                    self.advance();
+                    depth -= 1;
+                }
+                (Some('\n'), _) | (Some('\r'), _) => {
+                    self.skip_newline();
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    fn skip_whitespace(&mut self) {
+    fn skip_whitespace(&mut self) -> Result<(), TokenisationError> {
         loop {
             let c = self.peek(0);
             if c.is_none() {
                 break;
             }
             match c.unwrap() {
-                ' ' | '\t' | '\r' => {
+                ' ' | '\t' => {
                     self.advance();
                 }
-                '\n' => {
-                    self.advance();
-                    self.line += 1;
-                    self.column = 1;
+                '\n' | '\r' => {
+                    self.skip_newline();
+                }
+                '/' if self.peek(1) == Some('/') => {
+                    while !self.is_line_end() {
+                        self.advance();
+                    }
+                }
+                '/' if self.peek(1) == Some('*') => {
+                    self.skip_block_comment()?;
                 }
                 _ => {
                     break;
                 }
             }
         }
+
+        Ok(())
+    }
+
+    fn skip_shebang(&mut self) {
+        if self.index != 0 || self.peek(0) != Some('#') || self.peek(1) != Some('!') {
+            return;
+        }
+
+        while !self.is_line_end() {
+            self.advance();
+        }
     }
 
     fn is_end(&self) -> bool {
@@ -110,31 +242,197 @@ impl Lexer {
         }
 
         match c.unwrap() {
-            ' ' | '\t' | '\r' | '\n' | '(' | ')' | '{' | '}' | '=' | '+' | '-' | '*' | '/'
-            | '%' | '^' | ',' | '.' | '!' | '>' | '<' | '&' | '|' => true,
+            ' ' | '\t' | '\r' | '\n' => true,
+            '(' | ')' | '{' | '}' | '[' | ']' | '=' | '+' | '-' | '*' | '/' | '%' | '^' | ','
+            | '.' | ':' | ';' | '!' | '>' | '<' | '&' | '|' | '?' => true,
             _ => false,
         }
     }
 
-    fn error(&self, message: String) -> TokenisationError {
+    fn line_context(&self, line: u32) -> String {
+        self.lines
+            .get((line - 1) as usize)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn error_at(
+        &self,
+        code: ErrorCode,
+        message: String,
+        line: u32,
+        column: u32,
+        index: u32,
+    ) -> TokenisationError {
         TokenisationError {
-            line: self.line,
-            column: self.column,
-            index: self.index,
+            code,
+            line,
+            column,
+            index,
             filename: self.filename.clone(),
             message,
-            line_context: self.lines[(self.line - 1) as usize].clone(),
+            line_context: self.line_context(line),
+            end: None,
         }
     }
 
-    fn make_token(&self, token_type: TokenType, length: u32) -> Token {
-        Token {
-            token_type,
+    fn error(&self, code: ErrorCode, message: String) -> TokenisationError {
+        self.error_at(code, message, self.line, self.column, self.index)
+    }
+
+    fn error_spanning(
+        &self,
+        code: ErrorCode,
+        message: String,
+        line: u32,
+        column: u32,
+        index: u32,
+    ) -> TokenisationError {
+        let mut error = self.error_at(code, message, line, column, index);
+        error.end = Some(SpanEnd {
             line: self.line,
             column: self.column,
-            index: self.index,
+            line_context: self.line_context(self.line),
+        });
+        error
+    }
+
+    fn check_token_length(&self, length: usize) -> Result<(), TokenisationError> {
+        match self.max_token_length {
+            Some(max) if length > max as usize => Err(self.error(
+                ErrorCode::TokenTooLong,
+                format!("Token exceeds maximum length of {} characters", max),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_line_lengths(&self) -> Result<(), TokenisationError> {
+        let max = match self.max_line_length {
+            Some(max) => max,
+            None => return Ok(()),
+        };
+
+        let mut line = 1;
+        let mut column = 1;
+
+        for (index, &c) in self.source.iter().enumerate() {
+            if c == '\r' && self.source.get(index + 1) == Some(&'\n') {
+                continue;
+            }
+
+            if c == '\n' || c == '\r' {
+                line += 1;
+                column = 1;
+                continue;
+            }
+
+            if column > max {
+                return Err(self.error_at(
+                    ErrorCode::LineTooLong,
+                    format!("Line exceeds maximum length of {} characters", max),
+                    line,
+                    column,
+                    index as u32,
+                ));
+            }
+
+            column += 1;
+        }
+
+        Ok(())
+    }
+
+    fn make_token(&self, token_type: TokenType) -> Token {
+        Token {
+            token_type,
+            line: self.token_line,
+            column: self.token_column,
+            index: self.token_index,
             filename: self.filename.clone(),
-            length,
+            length: self.index - self.token_index,
+        }
+    }
+
+    fn skip_separator(
+        &mut self,
+        previous: Option<char>,
+        radix: u32,
+    ) -> Result<(), TokenisationError> {
+        let previous_is_digit = previous.is_some_and(|c| c.is_digit(radix));
+        let next_is_digit = self.peek(1).is_some_and(|c| c.is_digit(radix));
+
+        if !previous_is_digit || !next_is_digit {
+            return Err(self.error(
+                ErrorCode::InvalidNumber,
+                "Digit separator '_' must be between two digits".to_string(),
+            ));
+        }
+
+        self.advance();
+        Ok(())
+    }
+
+    fn get_radix_number(&mut self, radix: u32) -> TokenisationResult {
+        let mut digits = String::new();
+
+        let prefix: String = [self.peek(0).unwrap(), self.peek(1).unwrap()]
+            .iter()
+            .collect();
+        self.advance();
+        self.advance();
+
+        loop {
+            let c = self.peek(0);
+            if c == Some('_') {
+                self.skip_separator(digits.chars().last(), radix)?;
+                continue;
+            }
+
+            if c.is_none() || !c.unwrap().is_ascii_alphanumeric() {
+                break;
+            }
+
+            let c = c.unwrap();
+            if !c.is_digit(radix) {
+                return Err(self.error(
+                    ErrorCode::InvalidNumber,
+                    format!("Invalid digit for base {} literal: {}", radix, c),
+                ));
+            }
+
+            digits.push(c);
+            self.advance();
+        }
+
+        if digits.is_empty() {
+            return Err(self.error(
+                ErrorCode::InvalidNumber,
+                "Missing digits after ".to_string() + &prefix + " prefix",
+            ));
+        }
+
+        if !self.is_boundary() {
+            let c = self.peek(0).unwrap().to_string();
+            return Err(self.error(
+                ErrorCode::InvalidNumber,
+                "Unexpected character in numeric literal: ".to_string() + &c,
+            ));
+        }
+
+        self.make_int(&digits, radix, prefix + &digits)
+    }
+
+    // Magnitudes up to 2^63 are let through so the parser can fold '-9223372036854775808'
+    fn make_int(&self, digits: &str, radix: u32, literal: String) -> TokenisationResult {
+        match u64::from_str_radix(digits, radix) {
+            Ok(value) if value <= i64::MIN.unsigned_abs() => {
+                Ok(self.make_token(TokenType::Int(value)))
+            }
+            _ => Err(self.error(
+                ErrorCode::IntegerOutOfRange,
+                "Integer literal out of range: ".to_string() + &literal,
+            )),
         }
     }
 
@@ -142,6 +440,19 @@ impl Lexer {
         let mut number = String::new();
         let mut is_float = false;
 
+        if self.peek(0) == Some('0') {
+            let radix = match self.peek(1) {
+                Some('x') | Some('X') => Some(16),
+                Some('o') | Some('O') => Some(8),
+                Some('b') | Some('B') => Some(2),
+                _ => None,
+            };
+
+            if let Some(radix) = radix {
+                return self.get_radix_number(radix);
+            }
+        }
+
         loop {
             let c = self.peek(0);
             if c.is_none() {
@@ -152,6 +463,9 @@ impl Lexer {
                     number.push(c.unwrap());
                     self.advance();
                 }
+                '_' => {
+                    self.skip_separator(number.chars().last(), 10)?;
+                }
                 '.' => {
                     let next = self.peek(1);
                     if next.is_some() {
@@ -162,6 +476,7 @@ impl Lexer {
 
                     if is_float {
                         return Err(self.error(
+                            ErrorCode::InvalidNumber,
                             "Illegal second decimal point in float literal: '.'".to_string(),
                         ));
                     }
@@ -175,21 +490,67 @@ impl Lexer {
             }
         }
 
+        if self.peek(0) == Some('e') || self.peek(0) == Some('E') {
+            number.push('e');
+            self.advance();
+            is_float = true;
+
+            if self.peek(0) == Some('+') || self.peek(0) == Some('-') {
+                number.push(self.peek(0).unwrap());
+                self.advance();
+            }
+
+            if !self.peek(0).is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error(
+                    ErrorCode::InvalidNumber,
+                    "Missing digits in float literal exponent".to_string(),
+                ));
+            }
+
+            loop {
+                let c = self.peek(0);
+                if c == Some('_') {
+                    self.skip_separator(number.chars().last(), 10)?;
+                    continue;
+                }
+
+                if !c.is_some_and(|c| c.is_ascii_digit()) {
+                    break;
+                }
+
+                number.push(c.unwrap());
+                self.advance();
+            }
+        }
+
+        let suffix = self.peek(0);
+        if suffix == Some('i') || suffix == Some('f') {
+            self.advance();
+
+            if suffix == Some('i') && is_float {
+                return Err(self.error(
+                    ErrorCode::InvalidNumber,
+                    "Integer suffix on float literal: ".to_string() + &number + "i",
+                ));
+            }
+
+            if suffix == Some('f') {
+                is_float = true;
+            }
+        }
+
         if !self.is_boundary() {
             let c = self.peek(0).unwrap().to_string();
-            return Err(self.error("Unexpected character in numeric literal: ".to_string() + &c));
+            return Err(self.error(
+                ErrorCode::InvalidNumber,
+                "Unexpected character in numeric literal: ".to_string() + &c,
+            ));
         }
 
         if is_float {
-            Ok(self.make_token(
-                TokenType::Float(number.parse().unwrap()),
-                number.len().try_into().unwrap(),
-            ))
+            Ok(self.make_token(TokenType::Float(number.parse().unwrap())))
         } else {
-            Ok(self.make_token(
-                TokenType::Int(number.parse().unwrap()),
-                number.len().try_into().unwrap(),
-            ))
+            self.make_int(&number, 10, number.clone())
         }
     }
 
@@ -205,6 +566,7 @@ impl Lexer {
                 'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => {
                     ident.push(c.unwrap());
                     self.advance();
+                    self.check_token_length(ident.len())?;
                 }
                 _ => {
                     break;
@@ -214,23 +576,28 @@ impl Lexer {
 
         if !self.is_boundary() {
             let c = self.peek(0).unwrap().to_string();
-            return Err(self.error("Unexpected character in identifier: ".to_string() + &c));
+            return Err(self.error(
+                ErrorCode::UnexpectedCharacter,
+                "Unexpected character in identifier: ".to_string() + &c,
+            ));
         }
 
-        let ident_size = ident.len();
+        let token_type = match ident.as_str() {
+            "if" => TokenType::KWIf,
+            "else" => TokenType::KWElse,
+            "for" => TokenType::KWFor,
+            "return" => TokenType::KWReturn,
+            "break" => TokenType::KWBreak,
+            "continue" => TokenType::KWContinue,
+            "in" => TokenType::KWIn,
+            "fn" => TokenType::KWFn,
+            "true" => TokenType::Bool(true),
+            "false" => TokenType::Bool(false),
+            "_" => TokenType::Underscore,
+            _ => TokenType::Ident(ident),
+        };
 
-        match ident.as_str() {
-            "if" => return Ok(self.make_token(TokenType::KWIf, 2)),
-            "else" => return Ok(self.make_token(TokenType::KWElse, 4)),
-            "for" => return Ok(self.make_token(TokenType::KWFor, 3)),
-            "return" => return Ok(self.make_token(TokenType::KWReturn, 6)),
-            "break" => return Ok(self.make_token(TokenType::KWBreak, 5)),
-            "continue" => return Ok(self.make_token(TokenType::KWContinue, 8)),
-            "in" => return Ok(self.make_token(TokenType::KWIn, 2)),
-            "true" => return Ok(self.make_token(TokenType::Bool(true), 4)),
-            "false" => return Ok(self.make_token(TokenType::Bool(false), 5)),
-            _ => Ok(self.make_token(TokenType::Ident(ident), ident_size.try_into().unwrap())),
-        }
+        Ok(self.make_token(token_type))
     }
 
     fn get_single(&mut self) -> TokenisationResult {
@@ -238,23 +605,33 @@ impl Lexer {
         self.advance();
 
         match c {
-            '+' => Ok(self.make_token(TokenType::OpAdd, 1)),
-            '-' => Ok(self.make_token(TokenType::OpSub, 1)),
-            '*' => Ok(self.make_token(TokenType::OpMul, 1)),
-            '/' => Ok(self.make_token(TokenType::OpDiv, 1)),
-            '%' => Ok(self.make_token(TokenType::OpMod, 1)),
-            ',' => Ok(self.make_token(TokenType::Comma, 1)),
-            '.' => Ok(self.make_token(TokenType::Dot, 1)),
-            '!' => Ok(self.make_token(TokenType::OpNot, 1)),
-            '=' => Ok(self.make_token(TokenType::OpAssign, 1)),
-            '<' => Ok(self.make_token(TokenType::OpLt, 1)),
-            '>' => Ok(self.make_token(TokenType::OpGt, 1)),
-            '(' => Ok(self.make_token(TokenType::LParen, 1)),
-            ')' => Ok(self.make_token(TokenType::RParen, 1)),
-            '{' => Ok(self.make_token(TokenType::LBrace, 1)),
-            '}' => Ok(self.make_token(TokenType::RBrace, 1)),
-
-            _ => Err(self.error("Unexpected character: ".to_string() + &c.to_string())),
+            '+' => Ok(self.make_token(TokenType::OpAdd)),
+            '-' => Ok(self.make_token(TokenType::OpSub)),
+            '*' => Ok(self.make_token(TokenType::OpMul)),
+            '/' => Ok(self.make_token(TokenType::OpDiv)),
+            '%' => Ok(self.make_token(TokenType::OpMod)),
+            ',' => Ok(self.make_token(TokenType::Comma)),
+            '.' => Ok(self.make_token(TokenType::Dot)),
+            '!' => Ok(self.make_token(TokenType::OpNot)),
+            '=' => Ok(self.make_token(TokenType::OpAssign)),
+            '<' => Ok(self.make_token(TokenType::OpLt)),
+            '>' => Ok(self.make_token(TokenType::OpGt)),
+            '&' => Ok(self.make_token(TokenType::OpBitAnd)),
+            '|' => Ok(self.make_token(TokenType::OpBitOr)),
+            '^' => Ok(self.make_token(TokenType::OpBitXor)),
+            '(' => Ok(self.make_token(TokenType::LParen)),
+            ')' => Ok(self.make_token(TokenType::RParen)),
+            '{' => Ok(self.make_token(TokenType::LBrace)),
+            '}' => Ok(self.make_token(TokenType::RBrace)),
+            '[' => Ok(self.make_token(TokenType::LBracket)),
+            ']' => Ok(self.make_token(TokenType::RBracket)),
+            ':' => Ok(self.make_token(TokenType::Colon)),
+            ';' => Ok(self.make_token(TokenType::Semicolon)),
+
+            _ => Err(self.error(
+                ErrorCode::UnexpectedCharacter,
+                "Unexpected character: ".to_string() + &c.to_string(),
+            )),
         }
     }
 
@@ -272,151 +649,735 @@ impl Lexer {
             ('*', '*') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::OpPow, 2))
+                Ok(self.make_token(TokenType::OpPow))
             }
             ('=', '=') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::OpEq, 2))
+                Ok(self.make_token(TokenType::OpEq))
             }
             ('>', '=') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::OpGe, 2))
+                Ok(self.make_token(TokenType::OpGe))
             }
             ('<', '=') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::OpLe, 2))
+                Ok(self.make_token(TokenType::OpLe))
             }
             ('!', '=') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::OpNe, 2))
+                Ok(self.make_token(TokenType::OpNe))
             }
             ('&', '&') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::OpAnd, 2))
+                Ok(self.make_token(TokenType::OpAnd))
             }
             ('|', '|') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::OpOr, 2))
+                Ok(self.make_token(TokenType::OpOr))
+            }
+            ('+', '=') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OpAddAssign))
+            }
+            ('-', '=') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OpSubAssign))
+            }
+            ('*', '=') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OpMulAssign))
+            }
+            ('/', '=') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OpDivAssign))
+            }
+            ('%', '=') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OpModAssign))
+            }
+            ('<', '<') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OpShl))
+            }
+            ('>', '>') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OpShr))
             }
             ('=', '>') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::Arrow, 2))
+                Ok(self.make_token(TokenType::Arrow))
             }
             ('.', '.') => {
                 self.advance();
                 self.advance();
-                Ok(self.make_token(TokenType::Range, 2))
+                Ok(self.make_token(TokenType::Range))
+            }
+            ('?', '.') => {
+                self.advance();
+                self.advance();
+                Ok(self.make_token(TokenType::OptChain))
             }
 
             _ => self.get_single(),
         }
     }
 
+    fn get_escape(&mut self) -> Result<char, TokenisationError> {
+        let c = self.advance().unwrap();
+
+        match c {
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            '0' => Ok('\0'),
+            '\'' => Ok('\''),
+            '"' => Ok('"'),
+            '\\' => Ok('\\'),
+            'x' => self.get_byte_escape(),
+            'u' => self.get_unicode_escape(),
+            _ => Err(self.error(
+                ErrorCode::InvalidEscape,
+                "Invalid escape sequence: \\".to_string() + &c.to_string(),
+            )),
+        }
+    }
+
+    fn get_byte_escape(&mut self) -> Result<char, TokenisationError> {
+        let mut digits = String::new();
+
+        for _ in 0..2 {
+            let c = self.peek(0);
+
+            if c.is_none() || !c.unwrap().is_ascii_hexdigit() {
+                return Err(self.error(
+                    ErrorCode::InvalidEscape,
+                    "Expected two hex digits in \\x escape".to_string(),
+                ));
+            }
+
+            digits.push(c.unwrap());
+            self.advance();
+        }
+
+        Ok(char::from(u8::from_str_radix(&digits, 16).unwrap()))
+    }
+
+    fn get_unicode_escape(&mut self) -> Result<char, TokenisationError> {
+        if self.peek(0) != Some('{') {
+            return Err(self.error(
+                ErrorCode::InvalidEscape,
+                "Expected '{' after \\u in unicode escape".to_string(),
+            ));
+        }
+        self.advance();
+
+        let mut digits = String::new();
+
+        loop {
+            let c = self.peek(0);
+
+            if c == Some('}') {
+                break;
+            }
+
+            if c.is_none() || !c.unwrap().is_ascii_hexdigit() {
+                return Err(self.error(
+                    ErrorCode::InvalidEscape,
+                    "Expected hex digit or '}' in unicode escape".to_string(),
+                ));
+            }
+
+            if digits.len() == 6 {
+                return Err(self.error(
+                    ErrorCode::InvalidEscape,
+                    "Unicode escape must have at most 6 hex digits".to_string(),
+                ));
+            }
+
+            digits.push(c.unwrap());
+            self.advance();
+        }
+
+        if digits.is_empty() {
+            return Err(self.error(
+                ErrorCode::InvalidEscape,
+                "Unicode escape must have at least 1 hex digit".to_string(),
+            ));
+        }
+
+        let value = u32::from_str_radix(&digits, 16).unwrap();
+        let c = char::from_u32(value);
+
+        if c.is_none() {
+            return Err(self.error(
+                ErrorCode::InvalidEscape,
+                "Invalid unicode code point in escape: \\u{".to_string() + &digits + "}",
+            ));
+        }
+
+        self.advance();
+        Ok(c.unwrap())
+    }
+
     fn get_string(&mut self) -> TokenisationResult {
         let mut value = String::new();
+        let mut length = 0;
 
-        self.advance();
+        let (start_line, start_column, start_index) = (self.line, self.column, self.index);
 
-        let mut escape = false;
+        self.advance();
 
         loop {
             let c = self.advance();
 
             if c.is_none() {
-                return Err(self.error("Unterminated string literal".to_string()));
+                return Err(self.error_spanning(
+                    ErrorCode::UnterminatedString,
+                    "Unterminated string literal".to_string(),
+                    start_line,
+                    start_column,
+                    start_index,
+                ));
             }
 
             let c = c.unwrap();
 
-            if escape {
-                match c {
-                    'n' => value.push('\n'),
-                    'r' => value.push('\r'),
-                    't' => value.push('\t'),
-                    '0' => value.push('\0'),
-                    '\'' => value.push('\''),
-                    '"' => value.push('"'),
-                    '\\' => value.push('\\'),
-                    _ => {
-                        return Err(
-                            self.error("Invalid escape sequence: \\".to_string() + &c.to_string())
-                        )
-                    }
-                }
-                escape = false;
-                continue;
+            if c == '\n' || (c == '\r' && self.peek(0) != Some('\n')) {
+                self.line += 1;
+                self.column = 1;
             }
 
-            if c == '\\' {
-                escape = true;
+            if c == '"' {
+                break;
+            }
+
+            if c == '\\' && self.peek(0).is_some() {
+                value.push(self.get_escape()?);
+            } else {
+                value.push(c);
+            }
+
+            // Counted in characters, not the UTF-8 bytes value.len() would give
+            length += 1;
+            self.check_token_length(length)?;
+        }
+
+        Ok(self.make_token(TokenType::String(value)))
+    }
+
+    fn get_char(&mut self) -> TokenisationResult {
+        let mut value = String::new();
+
+        let (start_line, start_column, start_index) = (self.line, self.column, self.index);
+
+        self.advance();
+
+        loop {
+            if self.is_line_end() {
+                return Err(self.error_spanning(
+                    ErrorCode::UnterminatedChar,
+                    "Unterminated character literal".to_string(),
+                    start_line,
+                    start_column,
+                    start_index,
+                ));
+            }
+
+            let c = self.advance().unwrap();
+
+            if c == '\\' && self.peek(0).is_some() {
+                value.push(self.get_escape()?);
                 continue;
             }
 
-            if c == '"' {
+            if c == '\'' {
                 break;
             }
 
             value.push(c);
         }
 
-        let value_len = value.len();
+        let mut chars = value.chars();
 
-        Ok(self.make_token(
-            TokenType::String(value),
-            (value_len + 2).try_into().unwrap(),
-        ))
+        match (chars.next(), chars.next()) {
+            (None, _) => Err(self.error_spanning(
+                ErrorCode::InvalidChar,
+                "Empty character literal".to_string(),
+                start_line,
+                start_column,
+                start_index,
+            )),
+            (Some(c), None) => Ok(self.make_token(TokenType::Char(c))),
+            _ => Err(self.error_spanning(
+                ErrorCode::InvalidChar,
+                "Character literal too long".to_string(),
+                start_line,
+                start_column,
+                start_index,
+            )),
+        }
     }
 
     fn get_token(&mut self) -> TokenisationResult {
-        self.skip_whitespace();
+        self.skip_whitespace()?;
+
+        self.token_line = self.line;
+        self.token_column = self.column;
+        self.token_index = self.index;
 
         if self.is_end() {
-            return Ok(self.make_token(TokenType::EOF, 0));
+            return Ok(self.make_token(TokenType::EOF));
         }
 
         let c = self.peek(0).unwrap();
 
         match c {
-            '-' => {
-                let c = self.peek(1);
-                if c.is_some() && c.unwrap().is_ascii_digit() {
-                    self.advance();
-                    let mut token = self.get_number()?;
-                    match token.token_type {
-                        TokenType::Int(i) => token.token_type = TokenType::Int(-i),
-                        TokenType::Float(f) => token.token_type = TokenType::Float(-f),
-                        _ => {}
-                    }
-                    return Ok(token);
-                }
-                self.advance();
-                Ok(self.make_token(TokenType::OpSub, 1))
-            }
-            '+' | '*' | '/' | '%' | ',' | '.' | '!' | '=' | '<' | '>' | '&' | '|' | '(' | ')'
-            | '{' | '}' => self.get_multi(),
+            '+' | '-' | '*' | '/' | '%' | ',' | '.' | '!' | '=' | '<' | '>' | '&' | '|' | '^'
+            | '?' | '(' | ')' | '{' | '}' | '[' | ']' | ':' | ';' => self.get_multi(),
             '0'..='9' => self.get_number(),
-            'a'..='z' | 'A'..='Z' => self.get_ident(),
+            'a'..='z' | 'A'..='Z' | '_' => self.get_ident(),
             '"' => self.get_string(),
-            _ => Err(self.error("Unexpected character: ".to_string() + &c.to_string())),
+            '\'' => self.get_char(),
+            _ => Err(self.error(
+                ErrorCode::UnexpectedCharacter,
+                "Unexpected character: ".to_string() + &c.to_string(),
+            )),
         }
     }
 
-    pub fn tokenise(&mut self) -> Result<Vec<Token>, TokenisationError> {
+    fn recover(&mut self) {
+        if self.index == self.token_index {
+            self.advance();
+        }
+
+        while self.peek(0).is_some_and(|c| !c.is_whitespace()) {
+            self.advance();
+        }
+    }
+
+    pub fn tokenise(&mut self) -> Result<Vec<Token>, Vec<TokenisationError>> {
         let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+
+        if let Err(error) = self.check_line_lengths() {
+            if !self.recover_errors {
+                return Err(vec![error]);
+            }
+            errors.push(error);
+        }
+
+        self.skip_shebang();
         loop {
-            let token = self.get_token()?;
-            if token.token_type == TokenType::EOF {
+            let token = match self.get_token() {
+                Ok(token) => token,
+                Err(error) => {
+                    errors.push(error);
+                    if !self.recover_errors || self.is_end() {
+                        break;
+                    }
+
+                    self.recover();
+                    continue;
+                }
+            };
+
+            let is_eof = token.token_type == TokenType::EOF;
+            tokens.push(token);
+            if is_eof {
                 break;
             }
-            tokens.push(token);
         }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
         Ok(tokens)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(source: &str) -> Result<Vec<TokenType>, Vec<TokenisationError>> {
+        let mut lexer = Lexer::new("test.vp".to_string(), source.to_string());
+        let tokens = lexer.tokenise()?;
+
+        Ok(tokens.into_iter().map(|token| token.token_type).collect())
+    }
+
+    fn lex_error(source: &str) -> TokenisationError {
+        lex(source).unwrap_err().remove(0)
+    }
+
+    #[test]
+    fn minus_is_always_an_operator() {
+        assert_eq!(
+            lex("5-3").unwrap(),
+            vec![
+                TokenType::Int(5),
+                TokenType::OpSub,
+                TokenType::Int(3),
+                TokenType::EOF
+            ]
+        );
+        assert_eq!(
+            lex("a-1").unwrap(),
+            vec![
+                TokenType::Ident("a".to_string()),
+                TokenType::OpSub,
+                TokenType::Int(1),
+                TokenType::EOF
+            ]
+        );
+        assert_eq!(
+            lex("-x").unwrap(),
+            vec![
+                TokenType::OpSub,
+                TokenType::Ident("x".to_string()),
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn integer_above_i64_min_magnitude_is_out_of_range() {
+        let error = lex_error("9223372036854775809");
+        assert_eq!(error.code, ErrorCode::IntegerOutOfRange);
+    }
+
+    #[test]
+    fn i64_min_lexes_without_panicking() {
+        assert_eq!(
+            lex("-9223372036854775808").unwrap(),
+            vec![
+                TokenType::OpSub,
+                TokenType::Int(i64::MIN.unsigned_abs()),
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_string_spans_from_opening_quote() {
+        let error = lex_error("x = 1\ny = \"abc\ndef");
+        assert_eq!(error.code, ErrorCode::UnterminatedString);
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.line_context, "y = \"abc");
+
+        let end = error.end.unwrap();
+        assert_eq!((end.line, end.column), (3, 4));
+        assert_eq!(end.line_context, "def");
+    }
+
+    fn lex_limited(
+        source: &str,
+        max_token_length: u32,
+    ) -> Result<Vec<Token>, Vec<TokenisationError>> {
+        let mut lexer = Lexer::new("test.vp".to_string(), source.to_string());
+        lexer.max_token_length = Some(max_token_length);
+
+        lexer.tokenise()
+    }
+
+    #[test]
+    fn overlong_identifier_is_rejected() {
+        let error = lex_limited("abcdef", 4).unwrap_err().remove(0);
+        assert_eq!(error.code, ErrorCode::TokenTooLong);
+        assert!(lex_limited("abcd", 4).is_ok());
+    }
+
+    #[test]
+    fn string_length_counts_characters() {
+        assert!(lex_limited("\"éééé\"", 4).is_ok());
+
+        let error = lex_limited("\"ééééé\"", 4).unwrap_err().remove(0);
+        assert_eq!(error.code, ErrorCode::TokenTooLong);
+    }
+
+    #[test]
+    fn numeric_suffixes_pick_the_type() {
+        assert_eq!(
+            lex("5f").unwrap(),
+            vec![TokenType::Float(5.0), TokenType::EOF]
+        );
+        assert_eq!(lex("5i").unwrap(), vec![TokenType::Int(5), TokenType::EOF]);
+    }
+
+    #[test]
+    fn integer_suffix_on_float_is_rejected() {
+        let error = lex_error("5.0i");
+        assert_eq!(error.code, ErrorCode::InvalidNumber);
+        assert_eq!(error.message, "Integer suffix on float literal: 5.0i");
+    }
+
+    #[test]
+    fn bare_underscore_is_its_own_token() {
+        assert_eq!(
+            lex("_ _x a_b").unwrap(),
+            vec![
+                TokenType::Underscore,
+                TokenType::Ident("_x".to_string()),
+                TokenType::Ident("a_b".to_string()),
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn radix_literals() {
+        assert_eq!(
+            lex("0xFF 0o17 0b101").unwrap(),
+            vec![
+                TokenType::Int(255),
+                TokenType::Int(15),
+                TokenType::Int(5),
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_radix_digit_points_at_the_digit() {
+        let error = lex_error("0b102");
+        assert_eq!(error.code, ErrorCode::InvalidNumber);
+        assert_eq!(error.column, 5);
+    }
+
+    #[test]
+    fn radix_prefix_needs_digits() {
+        let error = lex_error("0x");
+        assert_eq!(error.message, "Missing digits after 0x prefix");
+    }
+
+    #[test]
+    fn radix_literal_must_end_at_a_boundary() {
+        let error = lex_error("0xFFg");
+        assert_eq!(error.code, ErrorCode::InvalidNumber);
+        assert_eq!(error.column, 5);
+    }
+
+    #[test]
+    fn digit_separators_are_skipped() {
+        assert_eq!(
+            lex("1_000 1_0.2_5").unwrap(),
+            vec![
+                TokenType::Int(1000),
+                TokenType::Float(10.25),
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn misplaced_digit_separators_are_rejected() {
+        for source in ["5_", "5__0", "5_.0", "5._0"] {
+            let error = lex_error(source);
+            assert_eq!(error.code, ErrorCode::InvalidNumber, "{}", source);
+        }
+    }
+
+    #[test]
+    fn exponent_makes_a_float() {
+        assert_eq!(
+            lex("2.5e-3 1E2").unwrap(),
+            vec![
+                TokenType::Float(2.5e-3),
+                TokenType::Float(100.0),
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn exponent_needs_digits() {
+        let error = lex_error("1e");
+        assert_eq!(error.message, "Missing digits in float literal exponent");
+    }
+
+    #[test]
+    fn char_literals() {
+        assert_eq!(
+            lex("'a' '\\n'").unwrap(),
+            vec![TokenType::Char('a'), TokenType::Char('\n'), TokenType::EOF]
+        );
+    }
+
+    #[test]
+    fn char_literal_needs_exactly_one_char() {
+        let error = lex_error("''");
+        assert_eq!(error.code, ErrorCode::InvalidChar);
+        assert_eq!(error.message, "Empty character literal");
+
+        let error = lex_error("'ab'");
+        assert_eq!(error.code, ErrorCode::InvalidChar);
+        assert_eq!(error.message, "Character literal too long");
+    }
+
+    #[test]
+    fn unterminated_char_literal() {
+        let error = lex_error("'a");
+        assert_eq!(error.code, ErrorCode::UnterminatedChar);
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        assert_eq!(
+            lex("\"\\u{41}\\u{1F600}\"").unwrap(),
+            vec![TokenType::String("A\u{1F600}".to_string()), TokenType::EOF]
+        );
+    }
+
+    #[test]
+    fn unicode_escape_must_be_a_scalar_value() {
+        let error = lex_error("\"\\u{D800}\"");
+        assert_eq!(
+            error.message,
+            "Invalid unicode code point in escape: \\u{D800}"
+        );
+
+        let error = lex_error("\"\\u{110000}\"");
+        assert_eq!(
+            error.message,
+            "Invalid unicode code point in escape: \\u{110000}"
+        );
+    }
+
+    #[test]
+    fn unicode_escape_needs_braces() {
+        let error = lex_error("\"\\u41\"");
+        assert_eq!(error.code, ErrorCode::InvalidEscape);
+        assert_eq!(error.column, 4);
+    }
+
+    #[test]
+    fn byte_escapes() {
+        assert_eq!(
+            lex("\"\\x41\\xE9\"").unwrap(),
+            vec![TokenType::String("Aé".to_string()), TokenType::EOF]
+        );
+    }
+
+    #[test]
+    fn byte_escape_needs_two_digits() {
+        let error = lex_error("\"\\xA\"");
+        assert_eq!(error.message, "Expected two hex digits in \\x escape");
+    }
+
+    #[test]
+    fn semicolons_separate_statements() {
+        assert_eq!(
+            lex("a = 1; b = 2;").unwrap(),
+            vec![
+                TokenType::Ident("a".to_string()),
+                TokenType::OpAssign,
+                TokenType::Int(1),
+                TokenType::Semicolon,
+                TokenType::Ident("b".to_string()),
+                TokenType::OpAssign,
+                TokenType::Int(2),
+                TokenType::Semicolon,
+                TokenType::EOF
+            ]
+        );
+    }
+
+    #[test]
+    fn recovery_reports_every_bad_character() {
+        let mut lexer = Lexer::new("test.vp".to_string(), "a = $\nb = @".to_string());
+        lexer.recover_errors = true;
+
+        let errors = lexer.tokenise().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!((errors[0].line, errors[0].column), (1, 5));
+        assert_eq!((errors[1].line, errors[1].column), (2, 5));
+    }
+
+    #[test]
+    fn crlf_is_a_single_line_break() {
+        let error = lex_error("a\r\n  $");
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = lex_error("a\r$");
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn shebang_line_is_skipped() {
+        let mut lexer = Lexer::new("test.vp".to_string(), "#!/usr/bin/env viper\nx".to_string());
+        let tokens = lexer.tokenise().unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::Ident("x".to_string()));
+        assert_eq!((tokens[0].line, tokens[0].column), (2, 1));
+    }
+
+    #[test]
+    fn shebang_after_the_first_line_is_an_error() {
+        let error = lex_error("x\n#!/usr/bin/env viper");
+        assert_eq!(error.code, ErrorCode::UnexpectedCharacter);
+    }
+
+    #[test]
+    fn error_message_includes_its_code() {
+        let error = lex_error("\"\\q\"");
+        assert_eq!(error.code, ErrorCode::InvalidEscape);
+        assert!(error.to_string().contains("[E0005]"));
+    }
+
+    fn lex_tokens(source: &str) -> Vec<Token> {
+        Lexer::new("test.vp".to_string(), source.to_string())
+            .tokenise()
+            .unwrap()
+    }
+
+    #[test]
+    fn nested_block_comments_are_consumed_whole() {
+        assert_eq!(
+            lex("/* a /* b */ c */ x").unwrap(),
+            vec![TokenType::Ident("x".to_string()), TokenType::EOF]
+        );
+    }
+
+    #[test]
+    fn newlines_in_block_comments_advance_the_line() {
+        let tokens = lex_tokens("/* a\nb\n */ x");
+        assert_eq!(tokens[0].token_type, TokenType::Ident("x".to_string()));
+        assert_eq!((tokens[0].line, tokens[0].column), (3, 5));
+    }
+
+    #[test]
+    fn unterminated_block_comment_points_at_the_opener() {
+        let error = lex_error("x = 1\n  /* a /* b */\nc");
+        assert_eq!(error.code, ErrorCode::UnterminatedComment);
+        assert_eq!(error.message, "Unterminated block comment");
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn line_comments_are_skipped() {
+        assert_eq!(lex("x = 1 // set x").unwrap(), lex("x = 1").unwrap());
+    }
+
+    #[test]
+    fn line_comment_ends_at_the_newline() {
+        let tokens = lex_tokens("// first\ny // at eof");
+        assert_eq!(tokens[0].token_type, TokenType::Ident("y".to_string()));
+        assert_eq!((tokens[0].line, tokens[0].column), (2, 1));
+        assert_eq!(tokens[1].token_type, TokenType::EOF);
+    }
+}
@@ -7,7 +7,15 @@ pub enum TokenType {
     LBrace, // {
     RBrace, // }
 
-    OpAssign, // =
+    LBracket, // [
+    RBracket, // ]
+
+    OpAssign,    // =
+    OpAddAssign, // +=
+    OpSubAssign, // -=
+    OpMulAssign, // *=
+    OpDivAssign, // /=
+    OpModAssign, // %=
 
     OpAdd, // +
     OpSub, // -
@@ -27,10 +35,20 @@ pub enum TokenType {
     OpOr,  // ||
     OpNot, // !
 
-    Comma, // ,
-    Dot,   // .
-    Arrow, // =>
-    Range, // ..
+    OpBitAnd, // &
+    OpBitOr,  // |
+    OpBitXor, // ^
+    OpShl,    // <<
+    OpShr,    // >>
+
+    Comma,      // ,
+    Colon,      // :
+    Semicolon,  // ; (terminates a statement)
+    Dot,        // .
+    Arrow,      // =>
+    Range,      // ..
+    OptChain,   // ?.
+    Underscore, // _
 
     KWIf,       // if
     KWElse,     // else
@@ -39,11 +57,13 @@ pub enum TokenType {
     KWBreak,    // break
     KWContinue, // continue
     KWIn,       // in
+    KWFn,       // fn
 
     Ident(String),
-    Int(i64),
+    Int(u64), // Magnitude only; the parser applies unary minus
     Float(f64),
     String(String),
+    Char(char),
 
     Bool(bool),
 
@@ -52,7 +72,76 @@ pub enum TokenType {
 
 impl fmt::Display for TokenType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        let symbol = match self {
+            TokenType::LParen => "(",
+            TokenType::RParen => ")",
+            TokenType::LBrace => "{",
+            TokenType::RBrace => "}",
+            TokenType::LBracket => "[",
+            TokenType::RBracket => "]",
+
+            TokenType::OpAssign => "=",
+            TokenType::OpAddAssign => "+=",
+            TokenType::OpSubAssign => "-=",
+            TokenType::OpMulAssign => "*=",
+            TokenType::OpDivAssign => "/=",
+            TokenType::OpModAssign => "%=",
+
+            TokenType::OpAdd => "+",
+            TokenType::OpSub => "-",
+            TokenType::OpMul => "*",
+            TokenType::OpDiv => "/",
+            TokenType::OpMod => "%",
+            TokenType::OpPow => "**",
+
+            TokenType::OpEq => "==",
+            TokenType::OpNe => "!=",
+            TokenType::OpLt => "<",
+            TokenType::OpLe => "<=",
+            TokenType::OpGt => ">",
+            TokenType::OpGe => ">=",
+
+            TokenType::OpAnd => "&&",
+            TokenType::OpOr => "||",
+            TokenType::OpNot => "!",
+
+            TokenType::OpBitAnd => "&",
+            TokenType::OpBitOr => "|",
+            TokenType::OpBitXor => "^",
+            TokenType::OpShl => "<<",
+            TokenType::OpShr => ">>",
+
+            TokenType::Comma => ",",
+            TokenType::Colon => ":",
+            TokenType::Semicolon => ";",
+            TokenType::Dot => ".",
+            TokenType::Arrow => "=>",
+            TokenType::Range => "..",
+            TokenType::OptChain => "?.",
+            TokenType::Underscore => "_",
+
+            TokenType::KWIf => "if",
+            TokenType::KWElse => "else",
+            TokenType::KWFor => "for",
+            TokenType::KWReturn => "return",
+            TokenType::KWBreak => "break",
+            TokenType::KWContinue => "continue",
+            TokenType::KWIn => "in",
+            TokenType::KWFn => "fn",
+
+            TokenType::Ident(name) => return write!(f, "identifier '{}'", name),
+            TokenType::Int(_) => return write!(f, "integer literal"),
+            TokenType::Float(_) => return write!(f, "float literal"),
+            TokenType::String(_) => return write!(f, "string literal"),
+            TokenType::Char(_) => return write!(f, "character literal"),
+
+            TokenType::Bool(true) => "true",
+            TokenType::Bool(false) => "false",
+
+            TokenType::EOF => return write!(f, "end of file"),
+        };
+
+        write!(f, "'{}'", symbol)
     }
 }
 
@@ -65,3 +154,20 @@ pub struct Token {
     pub filename: String,
     pub length: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_types_have_friendly_names() {
+        assert_eq!(TokenType::OpAdd.to_string(), "'+'");
+        assert_eq!(TokenType::KWIf.to_string(), "'if'");
+        assert_eq!(TokenType::Int(1).to_string(), "integer literal");
+        assert_eq!(
+            TokenType::Ident("foo".to_string()).to_string(),
+            "identifier 'foo'"
+        );
+        assert_eq!(TokenType::EOF.to_string(), "end of file");
+    }
+}
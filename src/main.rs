@@ -1,14 +1,33 @@
 use std::fs;
 
+mod diagnostics;
 mod lexer;
 mod parser;
 mod vm;
 
+fn explain(code: &str) -> String {
+    match diagnostics::ErrorCode::from_code(code) {
+        Some(code) => format!("{}\n\n{}", code, code.explanation()),
+        None => format!("Error: Unknown error code: {}", code),
+    }
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
     if args.len() < 2 {
         println!("Usage: {} <filename>", args[0]);
+        println!("       {} --explain <code>", args[0]);
+        return;
+    }
+
+    if args[1] == "--explain" {
+        if args.len() < 3 {
+            println!("Usage: {} --explain <code>", args[0]);
+            return;
+        }
+
+        println!("{}", explain(&args[2]));
         return;
     }
 
@@ -16,17 +35,17 @@ fn main() {
     let data = fs::read_to_string(filename).expect("Unable to read file.");
 
     let mut lex = lexer::Lexer::new(filename.to_string(), data.to_string());
+    lex.recover_errors = true;
     let lexer_result = lex.tokenise();
 
     if lexer_result.is_err() {
-        println!("Error: {}", lexer_result.err().unwrap());
+        for error in lexer_result.err().unwrap() {
+            println!("Error: {}\n", error);
+        }
         return;
     }
 
-    let mut parser = parser::Parser::new(
-        lexer_result.unwrap(),
-        data.lines().map(|s| s.to_string()).collect(),
-    );
+    let mut parser = parser::Parser::new(lexer_result.unwrap(), lex.lines.clone());
 
     let parser_result = parser.parse();
 
@@ -38,3 +57,17 @@ fn main() {
     // TODO: Parser
     println!("{:?}", parser_result.unwrap());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_prints_the_matching_text() {
+        let text = explain("e0005");
+        assert!(text.starts_with("E0005\n\n"));
+        assert!(text.ends_with(diagnostics::ErrorCode::InvalidEscape.explanation()));
+
+        assert_eq!(explain("E9999"), "Error: Unknown error code: E9999");
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
 pub enum Operator {
     Add,
     Sub,
@@ -17,24 +19,60 @@ pub enum Operator {
     Not,
 }
 
-#[derive(Debug)]
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Operator::Add => "+",
+            Operator::Sub => "-",
+            Operator::Mul => "*",
+            Operator::Div => "/",
+            Operator::Mod => "%",
+            Operator::Pow => "**",
+            Operator::Eq => "==",
+            Operator::Ne => "!=",
+            Operator::Lt => "<",
+            Operator::Le => "<=",
+            Operator::Gt => ">",
+            Operator::Ge => ">=",
+            Operator::And => "&&",
+            Operator::Or => "||",
+            Operator::Not => "!",
+        };
+
+        write!(f, "{}", symbol)
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub enum ExprNode {
     Int(i64),
     Float(f64),
     String(String),
+    Char(char),
     Bool(bool),
     Ident(String),
     Binary(Operator, Box<ExprNode>, Box<ExprNode>),
     Unary(Operator, Box<ExprNode>),
+
+    // Start, end
+    Range(Box<ExprNode>, Box<ExprNode>),
+
+    // Callee, args
+    Call(Box<ExprNode>, Vec<ExprNode>),
+
+    Array(Vec<ExprNode>),
+
+    // Target, index
+    Index(Box<ExprNode>, Box<ExprNode>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct FunctionParameter {
     pub name: String,
     pub typ: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum StmtNode {
     Expr(ExprNode),
     Assignment(String, ExprNode),
@@ -42,6 +80,9 @@ pub enum StmtNode {
     Break,
     Continue,
 
+    // Body, run in its own scope
+    Block(Vec<StmtNode>),
+
     // Cond, body, else
     If(ExprNode, Vec<StmtNode>, Vec<StmtNode>),
 
@@ -55,7 +96,7 @@ pub enum StmtNode {
     Context(String, u32),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct AST {
     pub nodes: Vec<StmtNode>,
 }
@@ -65,3 +106,33 @@ impl AST {
         AST { nodes: Vec::new() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_display_as_their_symbol() {
+        let symbols = [
+            (Operator::Add, "+"),
+            (Operator::Sub, "-"),
+            (Operator::Mul, "*"),
+            (Operator::Div, "/"),
+            (Operator::Mod, "%"),
+            (Operator::Pow, "**"),
+            (Operator::Eq, "=="),
+            (Operator::Ne, "!="),
+            (Operator::Lt, "<"),
+            (Operator::Le, "<="),
+            (Operator::Gt, ">"),
+            (Operator::Ge, ">="),
+            (Operator::And, "&&"),
+            (Operator::Or, "||"),
+            (Operator::Not, "!"),
+        ];
+
+        for (op, symbol) in symbols {
+            assert_eq!(op.to_string(), symbol);
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use colored::Colorize;
 use std::fmt;
 
+use super::super::diagnostics::ErrorCode;
 use super::super::lexer::*;
 use super::ast::*;
 
 #[derive(Debug)]
 pub struct ParsingError {
+    pub code: ErrorCode,
     pub line: u32,
     pub column: u32,
     pub index: u32,
@@ -18,16 +20,17 @@ pub struct ParsingError {
 impl fmt::Display for ParsingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut padding = String::new();
-        if self.column - 1 - self.token_size > 0 {
-            padding = " ".repeat((self.column - 1 - self.token_size) as usize);
+        if self.column - 1 > 0 {
+            padding = " ".repeat((self.column - 1) as usize);
         }
-        padding.push_str(&"~".repeat(self.token_size as usize));
+        padding.push_str(&"~".repeat(self.token_size.max(1) as usize));
 
         let arrow = "-->".blue().bold();
 
         write!(
             f,
-            "Parsing failed: {}\n {} {}:{}:{} ({})\n\n   {}\n   {}",
+            "Parsing failed [{}]: {}\n {} {}:{}:{} ({})\n\n   {}\n   {}",
+            self.code,
             self.message,
             arrow,
             self.filename,
@@ -41,8 +44,9 @@ impl fmt::Display for ParsingError {
 }
 
 impl ParsingError {
-    pub fn new(at: &Token, message: String, line: String) -> ParsingError {
+    pub fn new(at: &Token, code: ErrorCode, message: String, line: String) -> ParsingError {
         ParsingError {
+            code,
             line: at.line,
             column: at.column,
             index: at.index,
@@ -56,6 +60,54 @@ impl ParsingError {
 
 type ParsingResult<T> = Result<T, ParsingError>;
 
+const PREFIX_BP: u8 = 13;
+
+fn binary_operator(token_type: &TokenType) -> Option<Operator> {
+    match token_type {
+        TokenType::OpAdd => Some(Operator::Add),
+        TokenType::OpSub => Some(Operator::Sub),
+        TokenType::OpMul => Some(Operator::Mul),
+        TokenType::OpDiv => Some(Operator::Div),
+        TokenType::OpMod => Some(Operator::Mod),
+        TokenType::OpPow => Some(Operator::Pow),
+        TokenType::OpEq => Some(Operator::Eq),
+        TokenType::OpNe => Some(Operator::Ne),
+        TokenType::OpLt => Some(Operator::Lt),
+        TokenType::OpLe => Some(Operator::Le),
+        TokenType::OpGt => Some(Operator::Gt),
+        TokenType::OpGe => Some(Operator::Ge),
+        TokenType::OpAnd => Some(Operator::And),
+        TokenType::OpOr => Some(Operator::Or),
+        _ => None,
+    }
+}
+
+fn compound_assign_operator(token_type: &TokenType) -> Option<Operator> {
+    match token_type {
+        TokenType::OpAddAssign => Some(Operator::Add),
+        TokenType::OpSubAssign => Some(Operator::Sub),
+        TokenType::OpMulAssign => Some(Operator::Mul),
+        TokenType::OpDivAssign => Some(Operator::Div),
+        TokenType::OpModAssign => Some(Operator::Mod),
+        _ => None,
+    }
+}
+
+// Left and right binding power; a higher right power makes an operator left-associative
+fn binding_power(op: &Operator) -> (u8, u8) {
+    match op {
+        Operator::Or => (1, 2),
+        Operator::And => (3, 4),
+        Operator::Eq | Operator::Ne => (5, 6),
+        Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge => (7, 8),
+        Operator::Add | Operator::Sub => (9, 10),
+        Operator::Mul | Operator::Div | Operator::Mod => (11, 12),
+        // Binds tighter than unary operators (PREFIX_BP), so -2 ** 2 is -(2 ** 2)
+        Operator::Pow => (16, 15),
+        Operator::Not => (0, 0),
+    }
+}
+
 pub struct Parser {
     tokens: Vec<Token>,
     lines: Vec<String>,
@@ -72,26 +124,402 @@ impl Parser {
     }
 
     fn is_done(&self) -> bool {
-        self.index >= self.tokens.len()
+        self.tokens[self.index].token_type == TokenType::EOF
+    }
+
+    fn error(&self, code: ErrorCode, message: String) -> ParsingError {
+        self.error_at(self.index, code, message)
+    }
+
+    fn error_at(&self, index: usize, code: ErrorCode, message: String) -> ParsingError {
+        let token = &self.tokens[index.min(self.tokens.len() - 1)];
+        let line = self
+            .lines
+            .get((token.line - 1) as usize)
+            .cloned()
+            .unwrap_or_default();
+
+        ParsingError::new(token, code, message, line)
+    }
+
+    fn expect(&mut self, token_type: TokenType, message: &str) -> ParsingResult<()> {
+        let token = &self.tokens[self.index];
+
+        if token.token_type != token_type {
+            return Err(self.error(
+                ErrorCode::ExpectedToken,
+                message.to_string() + ", found " + &token.token_type.to_string(),
+            ));
+        }
+
+        self.index += 1;
+        Ok(())
     }
 
-    fn error(&self, message: String) -> ParsingError {
+    fn expect_ident(&mut self, message: &str) -> ParsingResult<String> {
+        let name = match &self.tokens[self.index].token_type {
+            TokenType::Ident(name) => name.clone(),
+            token_type => {
+                return Err(self.error(
+                    ErrorCode::ExpectedToken,
+                    message.to_string() + ", found " + &token_type.to_string(),
+                ))
+            }
+        };
+
+        self.index += 1;
+        Ok(name)
+    }
+
+    fn parse_atom(&mut self) -> ParsingResult<ExprNode> {
         let token = &self.tokens[self.index];
-        let line = self.lines[(token.line - 1) as usize].clone();
 
-        ParsingError::new(token, message, line)
+        let node = match &token.token_type {
+            TokenType::Int(i) => match i64::try_from(*i) {
+                Ok(i) => ExprNode::Int(i),
+                Err(_) => {
+                    return Err(self.error(
+                        ErrorCode::IntegerOutOfRange,
+                        format!("Integer literal out of range: {}", i),
+                    ))
+                }
+            },
+            TokenType::Float(f) => ExprNode::Float(*f),
+            TokenType::String(s) => ExprNode::String(s.clone()),
+            TokenType::Char(c) => ExprNode::Char(*c),
+            TokenType::Bool(b) => ExprNode::Bool(*b),
+            TokenType::Ident(name) => ExprNode::Ident(name.clone()),
+            TokenType::LParen => {
+                self.index += 1;
+                let expr = self.parse_expr(0)?;
+                self.expect(TokenType::RParen, "Expected ')' to close '('")?;
+                return Ok(expr);
+            }
+            TokenType::LBracket => {
+                self.index += 1;
+                let items =
+                    self.parse_list(TokenType::RBracket, "Expected ',' or ']' after array item")?;
+                return Ok(ExprNode::Array(items));
+            }
+            TokenType::OpSub | TokenType::OpNot => {
+                let op = if token.token_type == TokenType::OpSub {
+                    Operator::Sub
+                } else {
+                    Operator::Not
+                };
+
+                self.index += 1;
+
+                // i64::MIN has no positive counterpart, so '-9223372036854775808' is folded
+                // here instead of rejecting its magnitude. Only a bare literal operand is folded;
+                // if a call, index or '**' would extend the operand, it is parsed as usual.
+                if let (Operator::Sub, TokenType::Int(i)) =
+                    (&op, &self.tokens[self.index].token_type)
+                {
+                    let next = &self.tokens[self.index + 1].token_type;
+                    let extends_operand = matches!(next, TokenType::LParen | TokenType::LBracket)
+                        || binary_operator(next)
+                            .is_some_and(|op| binding_power(&op).0 >= PREFIX_BP);
+
+                    if *i == i64::MIN.unsigned_abs() && !extends_operand {
+                        self.index += 1;
+                        return Ok(ExprNode::Int(i64::MIN));
+                    }
+                }
+
+                let operand = self.parse_expr(PREFIX_BP)?;
+                return Ok(ExprNode::Unary(op, Box::new(operand)));
+            }
+            _ => {
+                return Err(self.error(
+                    ErrorCode::UnexpectedToken,
+                    "Expected expression, found ".to_string() + &token.token_type.to_string(),
+                ))
+            }
+        };
+
+        self.index += 1;
+        Ok(node)
+    }
+
+    // Comma separated expressions up to and including the closer, allowing a trailing comma
+    fn parse_list(&mut self, closer: TokenType, message: &str) -> ParsingResult<Vec<ExprNode>> {
+        let mut items = Vec::new();
+
+        while self.tokens[self.index].token_type != closer {
+            items.push(self.parse_expr(0)?);
+
+            if self.tokens[self.index].token_type != TokenType::Comma {
+                break;
+            }
+            self.index += 1;
+        }
+
+        self.expect(closer, message)?;
+        Ok(items)
+    }
+
+    fn parse_postfix(&mut self) -> ParsingResult<ExprNode> {
+        let mut expr = self.parse_atom()?;
+
+        loop {
+            match self.tokens[self.index].token_type {
+                TokenType::LParen => {
+                    self.index += 1;
+                    let args =
+                        self.parse_list(TokenType::RParen, "Expected ',' or ')' after argument")?;
+                    expr = ExprNode::Call(Box::new(expr), args);
+                }
+                TokenType::LBracket => {
+                    self.index += 1;
+                    let index = self.parse_expr(0)?;
+                    self.expect(TokenType::RBracket, "Expected ']' after index")?;
+                    expr = ExprNode::Index(Box::new(expr), Box::new(index));
+                }
+                _ => break,
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn parse_expr(&mut self, min_bp: u8) -> ParsingResult<ExprNode> {
+        let mut lhs = self.parse_postfix()?;
+
+        while let Some(op) = binary_operator(&self.tokens[self.index].token_type) {
+            let (left_bp, right_bp) = binding_power(&op);
+            if left_bp < min_bp {
+                break;
+            }
+
+            self.index += 1;
+            let rhs = self.parse_expr(right_bp)?;
+            lhs = ExprNode::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+
+        // Ranges bind loosest of all and do not chain
+        if min_bp == 0 && self.tokens[self.index].token_type == TokenType::Range {
+            self.index += 1;
+            let end = self.parse_expr(1)?;
+            lhs = ExprNode::Range(Box::new(lhs), Box::new(end));
+        }
+
+        Ok(lhs)
     }
 
     fn get_stmt(&mut self) -> ParsingResult<StmtNode> {
         let token = &self.tokens[self.index];
 
-        match token.token_type {
+        let stmt = match token.token_type {
+            TokenType::LBrace => Ok(StmtNode::Block(self.parse_block()?)),
+            TokenType::KWIf => self.parse_if(),
+            TokenType::KWFor => self.parse_for(),
+            TokenType::KWFn => self.parse_function(),
+            TokenType::KWReturn => self.parse_return(),
+            // Blocks consume their own '}', so any closer reaching here has nothing open
+            TokenType::RBrace | TokenType::RParen | TokenType::RBracket => {
+                let opener = match token.token_type {
+                    TokenType::RBrace => TokenType::LBrace,
+                    TokenType::RParen => TokenType::LParen,
+                    _ => TokenType::LBracket,
+                };
+
+                Err(self.error(
+                    ErrorCode::UnexpectedToken,
+                    format!(
+                        "Unexpected closing {} — no matching {}",
+                        token.token_type, opener
+                    ),
+                ))
+            }
+            TokenType::KWBreak => {
+                self.index += 1;
+                Ok(StmtNode::Break)
+            }
+            TokenType::KWContinue => {
+                self.index += 1;
+                Ok(StmtNode::Continue)
+            }
+            _ => self.parse_expr_stmt(),
+        }?;
+
+        // Statements may optionally end with a ';'
+        if self.tokens[self.index].token_type == TokenType::Semicolon {
+            self.index += 1;
+        }
+
+        Ok(stmt)
+    }
+
+    fn parse_if(&mut self) -> ParsingResult<StmtNode> {
+        self.index += 1;
+
+        let cond = self.parse_expr(0)?;
+        let body = self.parse_block_after("if condition")?;
+
+        let mut else_body = Vec::new();
+
+        if self.tokens[self.index].token_type == TokenType::KWElse {
+            self.index += 1;
+
+            if self.tokens[self.index].token_type == TokenType::KWIf {
+                else_body.push(self.parse_if()?);
+            } else {
+                else_body = self.parse_block_after("else")?;
+            }
+        }
+
+        Ok(StmtNode::If(cond, body, else_body))
+    }
+
+    // A bare return is an error; functions without a value to give back just end their body
+    fn parse_return(&mut self) -> ParsingResult<StmtNode> {
+        self.index += 1;
+
+        match self.tokens[self.index].token_type {
+            TokenType::RBrace | TokenType::Semicolon | TokenType::EOF => Err(self.error(
+                ErrorCode::ExpectedToken,
+                "Expected expression after 'return', found ".to_string()
+                    + &self.tokens[self.index].token_type.to_string(),
+            )),
+            _ => Ok(StmtNode::Return(self.parse_expr(0)?)),
+        }
+    }
+
+    fn parse_expr_stmt(&mut self) -> ParsingResult<StmtNode> {
+        let start = self.index;
+        let expr = self.parse_expr(0)?;
+
+        let token_type = &self.tokens[self.index].token_type;
+        let compound = compound_assign_operator(token_type);
+
+        if *token_type != TokenType::OpAssign && compound.is_none() {
+            return Ok(StmtNode::Expr(expr));
+        }
+
+        let name = match expr {
+            ExprNode::Ident(name) => name,
             _ => {
-                return Err(
-                    self.error("Unexpected token: ".to_string() + &token.token_type.to_string())
-                )
+                return Err(self.error_at(
+                    start,
+                    ErrorCode::InvalidAssignTarget,
+                    "Invalid assignment target, expected an identifier".to_string(),
+                ))
+            }
+        };
+
+        self.index += 1;
+        let value = self.parse_expr(0)?;
+
+        // 'x op= v' desugars to 'x = x op v'. Targets are bare identifiers, so reading the
+        // target twice is free of side effects; index targets will need a single evaluation.
+        let value = match compound {
+            Some(op) => {
+                ExprNode::Binary(op, Box::new(ExprNode::Ident(name.clone())), Box::new(value))
             }
+            None => value,
+        };
+
+        Ok(StmtNode::Assignment(name, value))
+    }
+
+    fn parse_for(&mut self) -> ParsingResult<StmtNode> {
+        self.index += 1;
+
+        let var = self.expect_ident("Expected loop variable after 'for'")?;
+
+        self.expect(TokenType::KWIn, "Expected 'in' after loop variable")?;
+
+        let iter = self.parse_expr(0)?;
+        let body = self.parse_block_after("for loop iterable")?;
+
+        Ok(StmtNode::For(var, iter, body))
+    }
+
+    fn parse_function(&mut self) -> ParsingResult<StmtNode> {
+        self.index += 1;
+
+        let name = self.expect_ident("Expected function name after 'fn'")?;
+        self.expect(TokenType::LParen, "Expected '(' after function name")?;
+
+        let mut params: Vec<FunctionParameter> = Vec::new();
+
+        while self.tokens[self.index].token_type != TokenType::RParen {
+            let name_index = self.index;
+            let param_name = self.expect_ident("Expected parameter name")?;
+
+            if params.iter().any(|param| param.name == param_name) {
+                return Err(self.error_at(
+                    name_index,
+                    ErrorCode::DuplicateParameter,
+                    format!("Duplicate parameter name '{}'", param_name),
+                ));
+            }
+
+            self.expect(TokenType::Colon, "Expected ':' after parameter name")?;
+            let typ = self.expect_ident("Expected parameter type after ':'")?;
+
+            params.push(FunctionParameter {
+                name: param_name,
+                typ,
+            });
+
+            if self.tokens[self.index].token_type != TokenType::Comma {
+                break;
+            }
+            self.index += 1;
+        }
+
+        self.expect(TokenType::RParen, "Expected ',' or ')' after parameter")?;
+
+        let mut ret = "void".to_string();
+
+        if self.tokens[self.index].token_type == TokenType::Arrow {
+            self.index += 1;
+            ret = self.expect_ident("Expected return type after '=>'")?;
         }
+
+        let body = self.parse_block_after("function signature")?;
+
+        Ok(StmtNode::Function(name, params, ret, body))
+    }
+
+    fn parse_block_after(&mut self, what: &str) -> ParsingResult<Vec<StmtNode>> {
+        if self.tokens[self.index].token_type != TokenType::LBrace {
+            return Err(self.error(
+                ErrorCode::ExpectedToken,
+                format!(
+                    "Expected '{{' after {}, found {}",
+                    what, self.tokens[self.index].token_type
+                ),
+            ));
+        }
+
+        self.parse_block()
+    }
+
+    fn parse_block(&mut self) -> ParsingResult<Vec<StmtNode>> {
+        self.expect(TokenType::LBrace, "Expected '{' to open block")?;
+
+        let mut body = Vec::new();
+
+        loop {
+            if self.is_done() {
+                return Err(self.error(
+                    ErrorCode::UnterminatedBlock,
+                    "Unterminated block, expected '}'".to_string(),
+                ));
+            }
+
+            if self.tokens[self.index].token_type == TokenType::RBrace {
+                self.index += 1;
+                break;
+            }
+
+            body.push(self.get_stmt()?);
+        }
+
+        Ok(body)
     }
 
     pub fn parse(&mut self) -> ParsingResult<AST> {
@@ -104,3 +532,397 @@ impl Parser {
         Ok(ast)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> ParsingResult<AST> {
+        let mut lexer = Lexer::new("test.vp".to_string(), source.to_string());
+        let tokens = lexer.tokenise().unwrap();
+
+        Parser::new(tokens, lexer.lines.clone()).parse()
+    }
+
+    fn parse_expr(source: &str) -> ExprNode {
+        match parse(source).unwrap().nodes.pop() {
+            Some(StmtNode::Expr(expr)) => expr,
+            node => panic!("expected an expression statement, got {:?}", node),
+        }
+    }
+
+    fn int(i: i64) -> Box<ExprNode> {
+        Box::new(ExprNode::Int(i))
+    }
+
+    fn binary(op: Operator, lhs: Box<ExprNode>, rhs: Box<ExprNode>) -> Box<ExprNode> {
+        Box::new(ExprNode::Binary(op, lhs, rhs))
+    }
+
+    fn parse_error(source: &str) -> ParsingError {
+        parse(source).unwrap_err()
+    }
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        assert_eq!(
+            parse_expr("2 + 3 * 4"),
+            *binary(Operator::Add, int(2), binary(Operator::Mul, int(3), int(4)))
+        );
+    }
+
+    #[test]
+    fn pow_is_right_associative() {
+        assert_eq!(
+            parse_expr("2 ** 3 ** 2"),
+            *binary(Operator::Pow, int(2), binary(Operator::Pow, int(3), int(2)))
+        );
+    }
+
+    #[test]
+    fn pow_binds_tighter_than_unary_minus() {
+        assert_eq!(
+            parse_expr("-2 ** 2"),
+            ExprNode::Unary(Operator::Sub, binary(Operator::Pow, int(2), int(2)))
+        );
+    }
+
+    #[test]
+    fn i64_min_magnitude_needs_a_minus() {
+        let error = parse_error("x = 9223372036854775808");
+        assert_eq!(error.code, ErrorCode::IntegerOutOfRange);
+        assert_eq!(error.column, 5);
+    }
+
+    #[test]
+    fn negative_i64_min_literal_folds() {
+        assert_eq!(
+            parse("x = -9223372036854775808").unwrap().nodes,
+            vec![StmtNode::Assignment(
+                "x".to_string(),
+                ExprNode::Int(i64::MIN)
+            )]
+        );
+    }
+
+    #[test]
+    fn i64_min_fold_keeps_postfix_precedence() {
+        for source in [
+            "x = -9223372036854775808[0]",
+            "x = -9223372036854775808(1)",
+            "x = -9223372036854775808 ** 2",
+        ] {
+            let error = parse_error(source);
+            assert_eq!(error.code, ErrorCode::IntegerOutOfRange, "{}", source);
+        }
+
+        assert_eq!(
+            parse_expr("-5[0]"),
+            ExprNode::Unary(Operator::Sub, Box::new(ExprNode::Index(int(5), int(0))))
+        );
+    }
+
+    #[test]
+    fn char_literal_is_an_atom() {
+        assert_eq!(
+            parse("c = 'a'").unwrap().nodes,
+            vec![StmtNode::Assignment("c".to_string(), ExprNode::Char('a'))]
+        );
+    }
+
+    #[test]
+    fn assignment_to_identifier() {
+        assert_eq!(
+            parse("x = 1 + 2").unwrap().nodes,
+            vec![StmtNode::Assignment(
+                "x".to_string(),
+                *binary(Operator::Add, int(1), int(2))
+            )]
+        );
+    }
+
+    #[test]
+    fn assignment_to_literal_is_rejected() {
+        let error = parse_error("1 = 2");
+        assert_eq!(error.code, ErrorCode::InvalidAssignTarget);
+        assert_eq!(error.column, 1);
+    }
+
+    #[test]
+    fn compound_assignment_desugars() {
+        assert_eq!(
+            parse("x = 1\nx += 1").unwrap().nodes,
+            vec![
+                StmtNode::Assignment("x".to_string(), ExprNode::Int(1)),
+                StmtNode::Assignment("x".to_string(), *binary(Operator::Add, ident("x"), int(1))),
+            ]
+        );
+        assert_eq!(
+            parse("x %= 2 * y").unwrap().nodes,
+            vec![StmtNode::Assignment(
+                "x".to_string(),
+                *binary(
+                    Operator::Mod,
+                    ident("x"),
+                    binary(Operator::Mul, int(2), ident("y"))
+                )
+            )]
+        );
+    }
+
+    #[test]
+    fn compound_assignment_to_non_identifier_is_rejected() {
+        let error = parse_error("a[0] += 1");
+        assert_eq!(error.code, ErrorCode::InvalidAssignTarget);
+        assert_eq!(error.column, 1);
+    }
+
+    #[test]
+    fn semicolons_terminate_statements() {
+        assert_eq!(
+            parse("x = 1; y = 2;").unwrap().nodes,
+            vec![
+                StmtNode::Assignment("x".to_string(), ExprNode::Int(1)),
+                StmtNode::Assignment("y".to_string(), ExprNode::Int(2)),
+            ]
+        );
+        assert_eq!(
+            parse("{ x; }").unwrap().nodes,
+            vec![StmtNode::Block(vec![StmtNode::Expr(ExprNode::Ident(
+                "x".to_string()
+            ))])]
+        );
+    }
+
+    fn ident(name: &str) -> Box<ExprNode> {
+        Box::new(ExprNode::Ident(name.to_string()))
+    }
+
+    #[test]
+    fn if_without_else_has_empty_else_body() {
+        assert_eq!(
+            parse("if x { return 1 }").unwrap().nodes,
+            vec![StmtNode::If(
+                *ident("x"),
+                vec![StmtNode::Return(ExprNode::Int(1))],
+                vec![]
+            )]
+        );
+    }
+
+    #[test]
+    fn else_if_chains_nest_in_else_body() {
+        assert_eq!(
+            parse("if a {} else if b {} else {}").unwrap().nodes,
+            vec![StmtNode::If(
+                *ident("a"),
+                vec![],
+                vec![StmtNode::If(*ident("b"), vec![], vec![])]
+            )]
+        );
+    }
+
+    #[test]
+    fn if_without_braces_points_at_found_token() {
+        let error = parse_error("if x y");
+        assert_eq!(error.code, ErrorCode::ExpectedToken);
+        assert_eq!(error.column, 6);
+    }
+
+    #[test]
+    fn for_loop_over_range() {
+        assert_eq!(
+            parse("for i in 0..10 { }").unwrap().nodes,
+            vec![StmtNode::For(
+                "i".to_string(),
+                ExprNode::Range(int(0), int(10)),
+                vec![]
+            )]
+        );
+    }
+
+    #[test]
+    fn for_loop_without_in_is_rejected() {
+        let error = parse_error("for i 0..10 { }");
+        assert_eq!(
+            error.message,
+            "Expected 'in' after loop variable, found integer literal"
+        );
+        assert_eq!(error.column, 7);
+    }
+
+    #[test]
+    fn zero_arg_function_defaults_to_void() {
+        assert_eq!(
+            parse("fn main() { }").unwrap().nodes,
+            vec![StmtNode::Function(
+                "main".to_string(),
+                vec![],
+                "void".to_string(),
+                vec![]
+            )]
+        );
+    }
+
+    #[test]
+    fn two_arg_function_with_return_type() {
+        let param = |name: &str, typ: &str| FunctionParameter {
+            name: name.to_string(),
+            typ: typ.to_string(),
+        };
+
+        assert_eq!(
+            parse("fn add(a: Int, b: Float) => Float { return a + b }")
+                .unwrap()
+                .nodes,
+            vec![StmtNode::Function(
+                "add".to_string(),
+                vec![param("a", "Int"), param("b", "Float")],
+                "Float".to_string(),
+                vec![StmtNode::Return(*binary(
+                    Operator::Add,
+                    ident("a"),
+                    ident("b")
+                ))]
+            )]
+        );
+    }
+
+    #[test]
+    fn duplicate_parameter_is_reported_at_second_occurrence() {
+        let error = parse_error("fn f(a: int, a: int) { }");
+        assert_eq!(error.code, ErrorCode::DuplicateParameter);
+        assert_eq!(error.message, "Duplicate parameter name 'a'");
+        assert_eq!(error.column, 14);
+
+        assert!(parse("fn f(a: int, b: int) { }").is_ok());
+    }
+
+    #[test]
+    fn untyped_parameter_is_rejected() {
+        let error = parse_error("fn f(a) { }");
+        assert_eq!(
+            error.message,
+            "Expected ':' after parameter name, found ')'"
+        );
+    }
+
+    #[test]
+    fn return_takes_an_expression() {
+        assert_eq!(
+            parse("return x + 1").unwrap().nodes,
+            vec![StmtNode::Return(*binary(Operator::Add, ident("x"), int(1)))]
+        );
+    }
+
+    #[test]
+    fn bare_return_is_rejected() {
+        let error = parse_error("fn f() { return }");
+        assert_eq!(
+            error.message,
+            "Expected expression after 'return', found '}'"
+        );
+        assert_eq!(error.column, 17);
+    }
+
+    #[test]
+    fn break_and_continue_parse_anywhere() {
+        assert_eq!(
+            parse("break continue").unwrap().nodes,
+            vec![StmtNode::Break, StmtNode::Continue]
+        );
+    }
+
+    #[test]
+    fn stray_closing_brace_is_reported_at_the_brace() {
+        let error = parse_error("x = 1\n  }");
+        assert_eq!(error.code, ErrorCode::UnexpectedToken);
+        assert_eq!(error.message, "Unexpected closing '}' — no matching '{'");
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    fn call(callee: Box<ExprNode>, args: Vec<ExprNode>) -> Box<ExprNode> {
+        Box::new(ExprNode::Call(callee, args))
+    }
+
+    #[test]
+    fn calls_chain_left_to_right() {
+        assert_eq!(
+            parse_expr("f(g(x))(y)"),
+            *call(
+                call(ident("f"), vec![*call(ident("g"), vec![*ident("x")])]),
+                vec![*ident("y")]
+            )
+        );
+    }
+
+    #[test]
+    fn call_arguments_allow_a_trailing_comma() {
+        assert_eq!(
+            parse_expr("f(a, b,)"),
+            *call(ident("f"), vec![*ident("a"), *ident("b")])
+        );
+        assert_eq!(parse_expr("f()"), *call(ident("f"), vec![]));
+    }
+
+    #[test]
+    fn index_into_array_literal() {
+        assert_eq!(
+            parse_expr("[1,2][0]"),
+            ExprNode::Index(
+                Box::new(ExprNode::Array(vec![ExprNode::Int(1), ExprNode::Int(2)])),
+                int(0)
+            )
+        );
+    }
+
+    #[test]
+    fn array_literals_allow_empty_and_trailing_comma() {
+        assert_eq!(parse_expr("[]"), ExprNode::Array(vec![]));
+        assert_eq!(parse_expr("[1,]"), ExprNode::Array(vec![ExprNode::Int(1)]));
+    }
+
+    #[test]
+    fn index_binds_tighter_than_binary_operators() {
+        assert_eq!(
+            parse_expr("a[0] * 2"),
+            *binary(
+                Operator::Mul,
+                Box::new(ExprNode::Index(ident("a"), int(0))),
+                int(2)
+            )
+        );
+    }
+
+    #[test]
+    fn mismatched_index_closer_is_reported_at_it() {
+        let error = parse_error("a[1)");
+        assert_eq!(error.message, "Expected ']' after index, found ')'");
+        assert_eq!(error.column, 4);
+    }
+
+    // Drops ANSI colour codes, which colored may or may not emit depending on the terminal
+    fn strip_colours(text: &str) -> String {
+        let mut plain = String::new();
+        let mut chars = text.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                plain.push(c);
+            }
+        }
+
+        plain
+    }
+
+    #[test]
+    fn error_at_keyword_underlines_exactly_it() {
+        let error = parse_error("x = if y {}");
+        assert_eq!((error.column, error.token_size), (5, 2));
+
+        let rendered = strip_colours(&error.to_string());
+        assert_eq!(rendered.lines().last(), Some("       ~~"));
+    }
+}